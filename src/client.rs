@@ -1,32 +1,82 @@
 use crate::util::http_method_str;
-use actix_http::{encoding::Decoder, Error, Payload, PayloadStream};
+use actix_http::{encoding::Decoder, Error, Payload, PayloadStream, RequestHeadType};
+use actix_service::{Service, Transform};
 use actix_web::{
     body::Body,
     client::{ClientRequest, ClientResponse, SendRequestError},
-    http::{HeaderName, HeaderValue},
+    http::{header::CONTENT_LENGTH, HeaderMap, HeaderName, HeaderValue, Method, Uri, Version},
     web::Bytes,
 };
-use futures::{future::TryFutureExt, Future, Stream};
+use awc::client::{ConnectRequest, ConnectResponse};
+use futures::{future::TryFutureExt, Future, FutureExt, Stream};
 use opentelemetry::{
     global,
+    metrics::Histogram,
     propagation::Injector,
     trace::{SpanKind, StatusCode, TraceContextExt, Tracer},
-    Context,
+    Context, KeyValue,
 };
 use opentelemetry_semantic_conventions::trace::{
-    HTTP_FLAVOR, HTTP_METHOD, HTTP_STATUS_CODE, HTTP_URL, NET_PEER_IP,
+    HTTP_FLAVOR, HTTP_METHOD, HTTP_RESPONSE_CONTENT_LENGTH, HTTP_STATUS_CODE, HTTP_URL,
+    NET_PEER_IP, NET_PEER_NAME, NET_PEER_PORT,
 };
 use serde::Serialize;
 use std::fmt;
+use std::net::SocketAddr;
+use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Instant;
+
+/// Name of the tracer/meter used to produce spans and metrics for instrumented `awc`
+/// client requests.
+const CLIENT_INSTRUMENTATION_NAME: &str = "actix-client";
+
+/// A predicate deciding which HTTP response status codes mark a client span as an
+/// error, per the [`InstrumentedClientRequest::with_error_statuses`] hook.
+type ErrorStatusPredicate = Arc<dyn Fn(u16) -> bool + Send + Sync>;
+
+/// Default error predicate: any status `>= 400`, per the OpenTelemetry HTTP semantic
+/// conventions for client spans.
+fn is_default_error_status(status: u16) -> bool {
+    status >= 400
+}
+
+const REQUEST_HEADER_PREFIX: &str = "http.request.header.";
+const RESPONSE_HEADER_PREFIX: &str = "http.response.header.";
+
+/// Headers whose values are replaced with [`REDACTED_HEADER_VALUE`] instead of being
+/// recorded on the span, regardless of what was passed to `with_request_headers` /
+/// `with_response_headers`.
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "cookie", "set-cookie"];
+const REDACTED_HEADER_VALUE: &str = "REDACTED";
 
 /// A wrapper for the actix-web [`ClientRequest`].
 ///
 /// [`ClientRequest`]: actix_web::client::ClientRequest
-#[derive(Debug)]
 pub struct InstrumentedClientRequest {
     cx: Context,
     request: ClientRequest,
+    error_statuses: ErrorStatusPredicate,
+    request_headers: Vec<String>,
+    response_headers: Vec<String>,
+    record_duration_metric: bool,
+    span_name_formatter: Option<SpanNameFormatter>,
+    tracer_name: String,
+}
+
+/// A customizer for the client span name, per
+/// [`InstrumentedClientRequest::with_span_name_formatter`].
+type SpanNameFormatter = Arc<dyn Fn(&ClientRequest) -> String + Send + Sync>;
+
+impl fmt::Debug for InstrumentedClientRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstrumentedClientRequest")
+            .field("cx", &self.cx)
+            .field("request", &self.request)
+            .finish()
+    }
 }
 
 /// OpenTelemetry extensions for actix-web's [`Client`].
@@ -84,7 +134,16 @@ pub trait ClientExt {
 
 impl ClientExt for ClientRequest {
     fn trace_request_with_context(self, cx: Context) -> InstrumentedClientRequest {
-        InstrumentedClientRequest { cx, request: self }
+        InstrumentedClientRequest {
+            cx,
+            request: self,
+            error_statuses: Arc::new(is_default_error_status),
+            request_headers: Vec::new(),
+            response_headers: Vec::new(),
+            record_duration_metric: false,
+            span_name_formatter: None,
+            tracer_name: CLIENT_INSTRUMENTATION_NAME.to_string(),
+        }
     }
 }
 
@@ -137,66 +196,470 @@ impl InstrumentedClientRequest {
             .await
     }
 
+    /// Configure which response status codes mark the span as [`StatusCode::Error`].
+    ///
+    /// Defaults to any status `>= 400`, per the OpenTelemetry HTTP semantic
+    /// conventions. Useful when a caller treats a particular 4xx response (e.g. a
+    /// `404` from a "does this exist" check) as an expected, non-error outcome.
+    pub fn with_error_statuses<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(u16) -> bool + Send + Sync + 'static,
+    {
+        self.error_statuses = Arc::new(predicate);
+        self
+    }
+
+    /// Record the given request headers as span attributes named
+    /// `http.request.header.<name>`.
+    ///
+    /// Multi-valued headers are joined with commas. Sensitive headers (e.g.
+    /// `authorization`, `cookie`, `set-cookie`) are recorded as `REDACTED` even when
+    /// they're listed here; headers not listed here are not recorded.
+    pub fn with_request_headers(mut self, headers: &[&str]) -> Self {
+        self.request_headers = headers.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Record the given response headers as span attributes named
+    /// `http.response.header.<name>`.
+    ///
+    /// Multi-valued headers are joined with commas. Sensitive headers (e.g.
+    /// `authorization`, `cookie`, `set-cookie`) are recorded as `REDACTED` even when
+    /// they're listed here; headers not listed here are not recorded.
+    pub fn with_response_headers(mut self, headers: &[&str]) -> Self {
+        self.response_headers = headers.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Opt into recording an `http.client.duration` histogram alongside the span,
+    /// using a [`Meter`] from `global::meter("actix-client")` looked up fresh for
+    /// every request.
+    ///
+    /// [`Meter`]: opentelemetry::metrics::Meter
+    pub fn with_metrics(mut self) -> Self {
+        self.record_duration_metric = true;
+        self
+    }
+
+    /// Override the span name, which otherwise defaults to
+    /// `"{METHOD} {scheme}://{authority}{path}"`. Use this to collapse dynamic path
+    /// segments (e.g. `/users/12345`) into a low-cardinality route template.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use actix_web::client;
+    /// use actix_web_opentelemetry::ClientExt;
+    ///
+    /// async fn execute_request(client: &client::Client) -> Result<(), client::SendRequestError> {
+    ///     let res = client.get("http://localhost:8080/users/12345")
+    ///         .trace_request()
+    ///         .with_span_name_formatter(|req| format!("{} /users/:id", req.get_method()))
+    ///         .send()
+    ///         .await?;
+    ///
+    ///     println!("Response: {:?}", res);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_span_name_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(&ClientRequest) -> String + Send + Sync + 'static,
+    {
+        self.span_name_formatter = Some(Arc::new(formatter));
+        self
+    }
+
+    /// Override the tracer name, which otherwise defaults to `"actix-client"`. Use
+    /// this to distinguish clients in multi-service or multi-client setups.
+    pub fn with_tracer_name(mut self, name: &str) -> Self {
+        self.tracer_name = name.to_string();
+        self
+    }
+
     async fn trace_request<F, R>(mut self, f: F) -> AwcResult
     where
         F: FnOnce(ClientRequest) -> R,
         R: Future<Output = AwcResult>,
     {
-        let tracer = global::tracer("actix-client");
-        let mut attributes = vec![
-            HTTP_METHOD.string(http_method_str(self.request.get_method())),
-            HTTP_URL.string(self.request.get_uri().to_string()),
-            HTTP_FLAVOR.string(format!("{:?}", self.request.get_version()).replace("HTTP/", "")),
-        ];
-
-        if let Some(peer_addr) = self.request.get_peer_addr() {
-            attributes.push(NET_PEER_IP.string(peer_addr.to_string()));
-        }
-
+        let tracer = global::tracer(self.tracer_name.clone());
+        let attributes = request_attributes(
+            self.request.get_method(),
+            self.request.get_uri(),
+            self.request.get_version(),
+            self.request.get_peer_addr(),
+        );
+        let span_name = match &self.span_name_formatter {
+            Some(formatter) => formatter(&self.request),
+            None => request_span_name(self.request.get_method(), self.request.get_uri()),
+        };
         let span = tracer
-            .span_builder(format!(
-                "{} {}{}{}",
-                self.request.get_method(),
-                self.request
-                    .get_uri()
-                    .scheme()
-                    .map(|s| format!("{}://", s.as_str()))
-                    .unwrap_or_else(String::new),
-                self.request
-                    .get_uri()
-                    .authority()
-                    .map(|s| s.as_str())
-                    .unwrap_or(""),
-                self.request.get_uri().path()
-            ))
+            .span_builder(span_name)
             .with_kind(SpanKind::Client)
             .with_attributes(attributes)
             .start(&tracer);
         let cx = self.cx.with_span(span);
+        let error_statuses = self.error_statuses;
+        let response_headers = self.response_headers;
+        let method = self.request.get_method().clone();
+        let peer_name = self.request.get_uri().host().map(|h| h.to_string());
+        let start_time = self.record_duration_metric.then(Instant::now);
+
+        for kv in header_attributes(
+            self.request.headers(),
+            &self.request_headers,
+            REQUEST_HEADER_PREFIX,
+        ) {
+            cx.span().set_attribute(kv);
+        }
 
         global::get_text_map_propagator(|injector| {
             injector.inject_context(&cx, &mut ActixClientCarrier::new(&mut self.request));
         });
 
         f(self.request)
-            .inspect_ok(|res| record_response(&res, &cx))
-            .inspect_err(|err| record_err(err, &cx))
+            .inspect_ok(|res| {
+                record_response(&res, &cx, &*error_statuses, &response_headers);
+                if let Some(start_time) = start_time {
+                    let status = Some(res.status().as_u16());
+                    record_client_duration(start_time, &method, status, peer_name.as_deref());
+                }
+            })
+            .inspect_err(|err| {
+                record_err(err, &cx);
+                if let Some(start_time) = start_time {
+                    record_client_duration(start_time, &method, None, peer_name.as_deref());
+                }
+            })
             .await
     }
 }
 
-fn record_response<T>(response: &ClientResponse<T>, cx: &Context) {
+/// Build the set of span attributes shared by [`InstrumentedClientRequest`] and
+/// [`TracingMiddleware`].
+fn request_attributes(
+    method: &Method,
+    uri: &Uri,
+    version: Version,
+    peer_addr: Option<SocketAddr>,
+) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        HTTP_METHOD.string(http_method_str(method)),
+        HTTP_URL.string(uri.to_string()),
+        HTTP_FLAVOR.string(format!("{:?}", version).replace("HTTP/", "")),
+    ];
+
+    if let Some(peer_addr) = peer_addr {
+        attributes.push(NET_PEER_IP.string(peer_addr.to_string()));
+    }
+
+    if let Some(host) = uri.host() {
+        attributes.push(NET_PEER_NAME.string(host.to_string()));
+    }
+
+    if let Some(port) = uri.port_u16() {
+        attributes.push(NET_PEER_PORT.i64(port as i64));
+    }
+
+    attributes
+}
+
+/// Build the span name shared by [`InstrumentedClientRequest`] and [`TracingMiddleware`].
+fn request_span_name(method: &Method, uri: &Uri) -> String {
+    format!(
+        "{} {}{}{}",
+        method,
+        uri.scheme()
+            .map(|s| format!("{}://", s.as_str()))
+            .unwrap_or_else(String::new),
+        uri.authority().map(|s| s.as_str()).unwrap_or(""),
+        uri.path()
+    )
+}
+
+/// Build span attributes for the `names` headers found in `headers`, redacting
+/// [`SENSITIVE_HEADERS`] and joining multi-valued headers with commas. Attribute keys
+/// are lowercased so the same header always produces the same key regardless of the
+/// casing passed to `with_request_headers`/`with_response_headers`.
+fn header_attributes(headers: &HeaderMap, names: &[String], prefix: &str) -> Vec<KeyValue> {
+    names
+        .iter()
+        .filter_map(|name| {
+            let values: Vec<&str> = headers
+                .get_all(name.as_str())
+                .filter_map(|v| v.to_str().ok())
+                .collect();
+            if values.is_empty() {
+                return None;
+            }
+
+            let name = name.to_lowercase();
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED_HEADER_VALUE.to_string()
+            } else {
+                values.join(",")
+            };
+
+            Some(KeyValue::new(format!("{}{}", prefix, name), value))
+        })
+        .collect()
+}
+
+/// Parse the `Content-Length` header, if present and a valid integer, for use as the
+/// `http.response_content_length` span attribute.
+fn parse_content_length(headers: &HeaderMap) -> Option<i64> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<i64>().ok())
+}
+
+fn record_response<T>(
+    response: &ClientResponse<T>,
+    cx: &Context,
+    is_error_status: &dyn Fn(u16) -> bool,
+    response_headers: &[String],
+) {
     let span = cx.span();
-    span.set_attribute(HTTP_STATUS_CODE.i64(response.status().as_u16() as i64));
+    let status = response.status();
+    span.set_attribute(HTTP_STATUS_CODE.i64(status.as_u16() as i64));
+    if is_error_status(status.as_u16()) {
+        span.set_status(
+            StatusCode::Error,
+            status.canonical_reason().unwrap_or("").to_string(),
+        );
+    }
+    if let Some(content_length) = parse_content_length(response.headers()) {
+        span.set_attribute(HTTP_RESPONSE_CONTENT_LENGTH.i64(content_length));
+    }
+    for kv in header_attributes(response.headers(), response_headers, RESPONSE_HEADER_PREFIX) {
+        span.set_attribute(kv);
+    }
     span.end();
 }
 
+/// Build the `http.client.duration` histogram instrument used by
+/// [`record_client_duration`].
+///
+/// Looked up from the global `MeterProvider` fresh on every call, the same way
+/// `global::tracer(...)` is looked up fresh for every span. This costs a redundant
+/// instrument registration per request, but it means `with_metrics()` always reports
+/// through whatever `MeterProvider` is currently installed instead of latching onto
+/// whichever one happened to be installed first — a caller that calls
+/// `global::set_meter_provider(...)` after the first traced request still gets their
+/// `http.client.duration` measurements.
+fn client_duration_histogram() -> Histogram<f64> {
+    global::meter(CLIENT_INSTRUMENTATION_NAME)
+        .f64_histogram("http.client.duration")
+        .with_description("Duration of outgoing HTTP client requests, in milliseconds")
+        .init()
+}
+
+/// Record an `http.client.duration` histogram measurement (in milliseconds) for a
+/// request that started at `start_time`, tagged with the method, status (when
+/// available), and peer host.
+fn record_client_duration(
+    start_time: Instant,
+    method: &Method,
+    status: Option<u16>,
+    peer_name: Option<&str>,
+) {
+    let mut attributes = vec![HTTP_METHOD.string(http_method_str(method))];
+    if let Some(status) = status {
+        attributes.push(HTTP_STATUS_CODE.i64(status as i64));
+    }
+    if let Some(peer_name) = peer_name {
+        attributes.push(NET_PEER_NAME.string(peer_name.to_string()));
+    }
+
+    let duration_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+    client_duration_histogram().record(duration_ms, &attributes);
+}
+
 fn record_err<T: fmt::Debug>(err: T, cx: &Context) {
     let span = cx.span();
     span.set_status(StatusCode::Error, format!("{:?}", err));
     span.end();
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_error_status_boundaries() {
+        assert!(!is_default_error_status(200));
+        assert!(!is_default_error_status(399));
+        assert!(is_default_error_status(400));
+        assert!(is_default_error_status(500));
+    }
+
+    #[test]
+    fn with_error_statuses_overrides_the_default_predicate() {
+        let instrumented = actix_web::client::Client::new()
+            .get("http://localhost/")
+            .trace_request()
+            .with_error_statuses(|status| status >= 400 && status != 404);
+
+        assert!(!(instrumented.error_statuses)(404));
+        assert!((instrumented.error_statuses)(500));
+        assert!(!(instrumented.error_statuses)(200));
+    }
+
+    #[test]
+    fn request_span_name_formats_method_scheme_authority_and_path() {
+        let uri = Uri::from_static("http://example.com:8080/users/1");
+
+        assert_eq!(
+            request_span_name(&Method::GET, &uri),
+            "GET http://example.com:8080/users/1"
+        );
+    }
+
+    #[test]
+    fn request_span_name_omits_scheme_and_authority_when_absent() {
+        let uri = Uri::from_static("/users/1");
+
+        assert_eq!(request_span_name(&Method::GET, &uri), "GET /users/1");
+    }
+
+    #[test]
+    fn with_span_name_formatter_overrides_the_default() {
+        let instrumented = actix_web::client::Client::new()
+            .get("http://localhost/users/123")
+            .trace_request()
+            .with_span_name_formatter(|req| format!("{} /users/:id", req.get_method()));
+
+        let formatter = instrumented
+            .span_name_formatter
+            .as_ref()
+            .expect("formatter should be set");
+
+        assert_eq!(formatter(&instrumented.request), "GET /users/:id");
+    }
+
+    #[test]
+    fn with_tracer_name_overrides_the_default() {
+        let instrumented = actix_web::client::Client::new()
+            .get("http://localhost/")
+            .trace_request()
+            .with_tracer_name("custom-tracer");
+
+        assert_eq!(instrumented.tracer_name, "custom-tracer");
+    }
+
+    #[test]
+    fn default_tracer_name_is_the_client_instrumentation_name() {
+        let instrumented = actix_web::client::Client::new()
+            .get("http://localhost/")
+            .trace_request();
+
+        assert_eq!(instrumented.tracer_name, CLIENT_INSTRUMENTATION_NAME);
+    }
+
+    #[test]
+    fn sensitive_headers_are_redacted_regardless_of_case() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_static("Bearer secret"),
+        );
+
+        let names = vec!["Authorization".to_string()];
+        let attrs = header_attributes(&headers, &names, REQUEST_HEADER_PREFIX);
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(attrs[0].key.as_str(), "http.request.header.authorization");
+        assert_eq!(attrs[0].value.to_string(), REDACTED_HEADER_VALUE);
+    }
+
+    #[test]
+    fn header_attribute_key_is_lowercased_regardless_of_requested_casing() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-request-id"),
+            HeaderValue::from_static("abc123"),
+        );
+
+        let names = vec!["X-Request-Id".to_string()];
+        let attrs = header_attributes(&headers, &names, REQUEST_HEADER_PREFIX);
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(attrs[0].key.as_str(), "http.request.header.x-request-id");
+    }
+
+    #[test]
+    fn header_absent_from_map_produces_no_attribute() {
+        let headers = HeaderMap::new();
+        let names = vec!["x-request-id".to_string()];
+
+        let attrs = header_attributes(&headers, &names, REQUEST_HEADER_PREFIX);
+
+        assert!(attrs.is_empty());
+    }
+
+    #[test]
+    fn multi_valued_header_is_comma_joined() {
+        let mut headers = HeaderMap::new();
+        headers.append(HeaderName::from_static("x-tag"), HeaderValue::from_static("a"));
+        headers.append(HeaderName::from_static("x-tag"), HeaderValue::from_static("b"));
+
+        let names = vec!["x-tag".to_string()];
+        let attrs = header_attributes(&headers, &names, REQUEST_HEADER_PREFIX);
+
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(attrs[0].value.to_string(), "a,b");
+    }
+
+    #[test]
+    fn request_attributes_include_net_peer_name_and_port_when_present() {
+        let uri = Uri::from_static("http://example.com:8080/users/1");
+        let attrs = request_attributes(&Method::GET, &uri, Version::HTTP_11, None);
+
+        let net_peer_name = attrs
+            .iter()
+            .find(|kv| kv.key.as_str() == NET_PEER_NAME.as_str());
+        let net_peer_port = attrs
+            .iter()
+            .find(|kv| kv.key.as_str() == NET_PEER_PORT.as_str());
+
+        assert_eq!(
+            net_peer_name.map(|kv| kv.value.to_string()),
+            Some("example.com".to_string())
+        );
+        assert_eq!(net_peer_port.map(|kv| kv.value.to_string()), Some("8080".to_string()));
+    }
+
+    #[test]
+    fn request_attributes_omit_net_peer_name_and_port_when_absent() {
+        let uri = Uri::from_static("/users/1");
+        let attrs = request_attributes(&Method::GET, &uri, Version::HTTP_11, None);
+
+        assert!(attrs
+            .iter()
+            .all(|kv| kv.key.as_str() != NET_PEER_NAME.as_str()));
+        assert!(attrs
+            .iter()
+            .all(|kv| kv.key.as_str() != NET_PEER_PORT.as_str()));
+    }
+
+    #[test]
+    fn parse_content_length_returns_value_when_present_and_valid() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("1234"));
+
+        assert_eq!(parse_content_length(&headers), Some(1234));
+    }
+
+    #[test]
+    fn parse_content_length_returns_none_when_absent_or_invalid() {
+        assert_eq!(parse_content_length(&HeaderMap::new()), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("not-a-number"));
+        assert_eq!(parse_content_length(&headers), None);
+    }
+}
+
 struct ActixClientCarrier<'a> {
     request: &'a mut ClientRequest,
 }
@@ -214,3 +677,194 @@ impl<'a> Injector for ActixClientCarrier<'a> {
         self.request.headers_mut().insert(header_name, header_value);
     }
 }
+
+/// Middleware that traces every request sent through an awc [`Client`], regardless of
+/// whether callers opt in with [`ClientExt::trace_request`]. Tunnel requests (such as
+/// WebSocket upgrades) are passed through without a span.
+///
+/// Wrap a client with it to get a span for every outgoing request, including ones made
+/// by third-party code sharing the same `Client`:
+///
+/// ```no_run
+/// use awc::Client;
+/// # use actix_web_opentelemetry::TracingMiddleware;
+///
+/// async fn send_request(client: &Client) -> Result<(), actix_web::client::SendRequestError> {
+///     // Every request through this client is traced by the middleware automatically;
+///     // no per-request opt-in is needed.
+///     let res = client.get("http://localhost:8080/").send().await?;
+///
+///     println!("Response: {:?}", res);
+///     Ok(())
+/// }
+///
+/// let client = Client::builder().wrap(TracingMiddleware::default()).finish();
+/// ```
+///
+/// [`ClientExt::trace_request`]/[`ClientExt::trace_request_with_context`] remain
+/// available as an alternative for callers who don't wrap their `Client` and want to
+/// pass an explicit [`Context`] per request:
+///
+/// ```no_run
+/// use actix_web::client;
+/// use actix_web_opentelemetry::ClientExt;
+///
+/// async fn send_request(client: &client::Client) -> Result<(), client::SendRequestError> {
+///     let res = client
+///         .get("http://localhost:8080/")
+///         .trace_request()
+///         .send()
+///         .await?;
+///
+///     println!("Response: {:?}", res);
+///     Ok(())
+/// }
+/// ```
+///
+/// Do not combine the two on the same request: [`InstrumentedClientRequest`] builds
+/// its span from the `Context` it's given and injects propagation headers directly,
+/// without making that `Context` "current". `TracingService` (installed by this
+/// middleware) has no visibility into it, so it builds an unrelated span from
+/// `Context::current()` and overwrites the `traceparent`/`tracestate` headers
+/// `ClientExt` just set. The result is two unlinked spans exported for one logical
+/// request, and the explicit `Context` passed to `ClientExt` never reaches the wire.
+///
+/// [`Client`]: awc::Client
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingMiddleware {
+    record_duration_metric: bool,
+}
+
+impl TracingMiddleware {
+    /// Opt into recording an `http.client.duration` histogram alongside the span for
+    /// every request that passes through this middleware, using a [`Meter`] from
+    /// `global::meter("actix-client")` looked up fresh for every request.
+    ///
+    /// [`Meter`]: opentelemetry::metrics::Meter
+    pub fn with_metrics(mut self) -> Self {
+        self.record_duration_metric = true;
+        self
+    }
+}
+
+impl<S> Transform<S> for TracingMiddleware
+where
+    S: Service<
+        Request = ConnectRequest,
+        Response = ConnectResponse,
+        Error = actix_http::error::SendRequestError,
+    >,
+    S::Future: 'static,
+{
+    type Request = ConnectRequest;
+    type Response = ConnectResponse;
+    type Error = S::Error;
+    type Transform = TracingService<S>;
+    type InitError = ();
+    type Future = futures::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        futures::future::ok(TracingService {
+            service,
+            record_duration_metric: self.record_duration_metric,
+        })
+    }
+}
+
+/// The [`Service`] produced by [`TracingMiddleware`].
+#[derive(Debug)]
+pub struct TracingService<S> {
+    service: S,
+    record_duration_metric: bool,
+}
+
+impl<S> Service for TracingService<S>
+where
+    S: Service<
+        Request = ConnectRequest,
+        Response = ConnectResponse,
+        Error = actix_http::error::SendRequestError,
+    >,
+    S::Future: 'static,
+{
+    type Request = ConnectRequest;
+    type Response = ConnectResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ConnectRequest) -> Self::Future {
+        let (mut head, body, addr) = match req {
+            ConnectRequest::Client(head, body, addr) => (head, body, addr),
+            ConnectRequest::Tunnel(..) => return Box::pin(self.service.call(req)),
+        };
+
+        let tracer = global::tracer(CLIENT_INSTRUMENTATION_NAME);
+        let attributes = request_attributes(
+            &head.as_ref().method,
+            &head.as_ref().uri,
+            head.as_ref().version,
+            addr,
+        );
+        let span = tracer
+            .span_builder(request_span_name(&head.as_ref().method, &head.as_ref().uri))
+            .with_kind(SpanKind::Client)
+            .with_attributes(attributes)
+            .start(&tracer);
+        let cx = Context::current_with_span(span);
+
+        global::get_text_map_propagator(|injector| {
+            injector.inject_context(&cx, &mut ConnectRequestCarrier::new(&mut head));
+        });
+
+        let method = head.as_ref().method.clone();
+        let peer_name = head.as_ref().uri.host().map(|h| h.to_string());
+        let start_time = self.record_duration_metric.then(Instant::now);
+
+        Box::pin(
+            self.service
+                .call(ConnectRequest::Client(head, body, addr))
+                .inspect(move |res| {
+                    let status = match res {
+                        Ok(ConnectResponse::Client(res)) => {
+                            record_response(res, &cx, &is_default_error_status, &[]);
+                            Some(res.status().as_u16())
+                        }
+                        Ok(ConnectResponse::Tunnel(..)) => {
+                            cx.span().end();
+                            None
+                        }
+                        Err(err) => {
+                            record_err(err, &cx);
+                            None
+                        }
+                    };
+
+                    if let Some(start_time) = start_time {
+                        record_client_duration(start_time, &method, status, peer_name.as_deref());
+                    }
+                }),
+        )
+    }
+}
+
+struct ConnectRequestCarrier<'a> {
+    head: &'a mut RequestHeadType,
+}
+
+impl<'a> ConnectRequestCarrier<'a> {
+    fn new(head: &'a mut RequestHeadType) -> Self {
+        ConnectRequestCarrier { head }
+    }
+}
+
+impl<'a> Injector for ConnectRequestCarrier<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        let header_name = HeaderName::from_str(key).expect("Must be header name");
+        let header_value = HeaderValue::from_str(&value).expect("Must be a header value");
+        self.head.headers_mut().insert(header_name, header_value);
+    }
+}